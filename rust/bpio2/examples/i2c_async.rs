@@ -0,0 +1,62 @@
+//! Read 8 bytes at address 0x10 from a 24-series EEPROM over I2C using [`bpio2::AsyncBusPirate`].
+//!
+//! Requires the `async` feature. Run with e.g. `cargo run --example i2c_async --features async`.
+
+#[cfg(feature = "async")]
+use bpio2::{AsyncBusPirate, ConfigurationRequestBuilder, DataRequestBuilder, ModeConfigurationBuilder};
+
+#[cfg(feature = "async")]
+#[tokio::main]
+async fn main() {
+    let Some(serial_port_path) = std::env::args().nth(1) else {
+        eprintln!("Provide the path to the serial port as the first argument.");
+        std::process::exit(-1);
+    };
+    let serial_port = tokio_serial::new(serial_port_path, 115_200)
+        .open_native_async()
+        .expect("Failed to open serial port.");
+
+    let mut bus_pirate = AsyncBusPirate::new(serial_port);
+
+    bus_pirate
+        .configure(|fbb| {
+            let mut mode_config = ModeConfigurationBuilder::new(fbb);
+            mode_config.add_speed(400_000);
+            mode_config.add_clock_stretch(false);
+            let mode_config = mode_config.finish();
+
+            let mode_name = fbb.create_string("I2C");
+
+            let mut configuration_request = ConfigurationRequestBuilder::new(fbb);
+            configuration_request.add_mode(mode_name);
+            configuration_request.add_mode_configuration(mode_config);
+            configuration_request.add_psu_enable(true);
+            configuration_request.add_psu_set_mv(3_300);
+            configuration_request.add_psu_set_ma(300);
+            configuration_request.add_pullup_enable(true);
+            configuration_request.finish()
+        })
+        .await
+        .expect("Failed to configure Bus Pirate for I2C.");
+
+    let bytes_read_from_eeprom = bus_pirate
+        .transfer(|fbb| {
+            let data_write = fbb.create_vector::<u8>(&[0xA0, 0x10]);
+
+            let mut data_request = DataRequestBuilder::new(fbb);
+            data_request.add_start_main(true);
+            data_request.add_data_write(data_write);
+            data_request.add_bytes_read(8);
+            data_request.add_stop_main(true);
+            data_request.finish()
+        })
+        .await
+        .expect("Failed to read from EEPROM.");
+
+    println!("{:X?}", bytes_read_from_eeprom);
+}
+
+#[cfg(not(feature = "async"))]
+fn main() {
+    eprintln!("This example requires the `async` feature: cargo run --example i2c_async --features async");
+}
@@ -0,0 +1,60 @@
+//! Read 8 bytes at address 0x10 from a 24-series EEPROM over I2C using
+//! [`embedded_hal::i2c::I2c`] through [`bpio2::BusPirateI2c`], rather than building BPIO2
+//! `DataRequest`s by hand as in `examples/i2c_session.rs`.
+//!
+//! Any `embedded-hal` I2C device driver can be pointed at `BusPirateI2c` the same way it would be
+//! pointed at an embassy or rp-hal I2C peripheral.
+
+use std::time::Duration;
+
+use bpio2::{BusPirate, BusPirateI2c, ConfigurationRequestBuilder, ModeConfigurationBuilder};
+use embedded_hal::i2c::I2c;
+
+fn main() {
+    let serial_port = open_serial_port();
+    let mut bus_pirate = BusPirate::new(serial_port);
+
+    bus_pirate
+        .configure(|fbb| {
+            let mut mode_config = ModeConfigurationBuilder::new(fbb);
+            mode_config.add_speed(400_000);
+            mode_config.add_clock_stretch(false);
+            let mode_config = mode_config.finish();
+
+            let mode_name = fbb.create_string("I2C");
+
+            let mut configuration_request = ConfigurationRequestBuilder::new(fbb);
+            configuration_request.add_mode(mode_name);
+            configuration_request.add_mode_configuration(mode_config);
+            configuration_request.add_psu_enable(true);
+            configuration_request.add_psu_set_mv(3_300);
+            configuration_request.add_psu_set_ma(300);
+            configuration_request.add_pullup_enable(true);
+            configuration_request.finish()
+        })
+        .expect("Failed to configure Bus Pirate for I2C.");
+
+    let mut i2c = BusPirateI2c::new(bus_pirate);
+
+    // 7-bit EEPROM address, starting byte address 0x10, then read 8 bytes back.
+    let mut eeprom_data = [0u8; 8];
+    i2c.write_read(0x50, &[0x10], &mut eeprom_data)
+        .expect("Failed to read from EEPROM.");
+
+    println!("{:X?}", eeprom_data);
+}
+
+fn open_serial_port() -> Box<dyn serialport::SerialPort> {
+    let Some(serial_port_path) = std::env::args().nth(1) else {
+        eprintln!("Provide the path to the serial port as the first argument.");
+        std::process::exit(-1);
+    };
+    let Ok(port) = serialport::new(serial_port_path, 115_200)
+        .timeout(Duration::from_millis(500))
+        .open()
+    else {
+        eprintln!("Failed to open serial port.");
+        std::process::exit(-2);
+    };
+    port
+}
@@ -0,0 +1,68 @@
+//! Read 8 bytes at address 0x10 from a 24-series EEPROM over I2C, using the [`bpio2::BusPirate`]
+//! session type instead of hand-rolling the COBS/flatbuffer round-trip.
+//!
+//! Compare with `examples/i2c.rs`, which shows what `BusPirate::configure` and
+//! `BusPirate::transfer` are doing under the hood.
+
+use std::time::Duration;
+
+use bpio2::{
+    BusPirate, ConfigurationRequestBuilder, DataRequestBuilder, ModeConfigurationBuilder,
+};
+
+fn main() {
+    let serial_port = open_serial_port();
+    let mut bus_pirate = BusPirate::new(serial_port);
+
+    bus_pirate
+        .configure(|fbb| {
+            let mut mode_config = ModeConfigurationBuilder::new(fbb);
+            mode_config.add_speed(400_000);
+            mode_config.add_clock_stretch(false);
+            let mode_config = mode_config.finish();
+
+            let mode_name = fbb.create_string("I2C");
+
+            let mut configuration_request = ConfigurationRequestBuilder::new(fbb);
+            configuration_request.add_mode(mode_name);
+            configuration_request.add_mode_configuration(mode_config);
+            configuration_request.add_psu_enable(true);
+            configuration_request.add_psu_set_mv(3_300);
+            configuration_request.add_psu_set_ma(300);
+            configuration_request.add_pullup_enable(true);
+            configuration_request.finish()
+        })
+        .expect("Failed to configure Bus Pirate for I2C.");
+
+    let bytes_read_from_eeprom = bus_pirate
+        .transfer(|fbb| {
+            // In I2C mode the address is always supplied in `data_write`, even for
+            // transactions that are just reads: the Bus Pirate sets the read/not-write bit.
+            let data_write = fbb.create_vector::<u8>(&[0xA0, 0x10]);
+
+            let mut data_request = DataRequestBuilder::new(fbb);
+            data_request.add_start_main(true);
+            data_request.add_data_write(data_write);
+            data_request.add_bytes_read(8);
+            data_request.add_stop_main(true);
+            data_request.finish()
+        })
+        .expect("Failed to read from EEPROM.");
+
+    println!("{:X?}", bytes_read_from_eeprom);
+}
+
+fn open_serial_port() -> Box<dyn serialport::SerialPort> {
+    let Some(serial_port_path) = std::env::args().nth(1) else {
+        eprintln!("Provide the path to the serial port as the first argument.");
+        std::process::exit(-1);
+    };
+    let Ok(port) = serialport::new(serial_port_path, 115_200)
+        .timeout(Duration::from_millis(500))
+        .open()
+    else {
+        eprintln!("Failed to open serial port.");
+        std::process::exit(-2);
+    };
+    port
+}
@@ -0,0 +1,10 @@
+/// Returns whether `address` falls in one of the I2C ranges reserved by the bus specification:
+/// the general-call/start-byte block (`0x00`-`0x07`) or the high-speed-mode/reserved block
+/// (`0x78`-`0x7F`). Ported from rp-hal's `i2c_reserved_addr`.
+///
+/// Address `0x00` (general call) is included here like any other reserved address; callers that
+/// genuinely want to broadcast a general call should go through a dedicated API rather than a
+/// normal addressed transaction.
+pub fn i2c_reserved_addr(address: u8) -> bool {
+    (address & 0x78) == 0 || (address & 0x78) == 0x78
+}
@@ -0,0 +1,162 @@
+//! Async transport, gated behind the `async` feature.
+//!
+//! Mirrors [`crate::BusPirate`], but built on `tokio_util::codec::Framed` instead of blocking
+//! `Read + Write`, so many Bus Pirates can be driven concurrently from one async runtime.
+
+use bytes::{BufMut, BytesMut};
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::codec::{Decoder, Encoder, Framed};
+use tokio_stream::StreamExt;
+
+use crate::{
+    BpioError, ConfigurationRequest, DataRequest, RequestPacket, RequestPacketArgs,
+    RequestPacketContents, ResponsePacketContents, root_as_response_packet,
+};
+
+/// A `tokio_util` codec for BPIO2's COBS-framed flatbuffers.
+///
+/// Encoding COBS-encodes a finished flatbuffer and appends the `0x00` sentinel. Decoding buffers
+/// bytes until a `0x00` sentinel appears, then COBS-decodes everything before it into an owned
+/// frame of flatbuffer bytes.
+#[derive(Debug, Default)]
+pub struct Bpio2Codec {
+    decode_buf: Vec<u8>,
+}
+
+impl Encoder<Vec<u8>> for Bpio2Codec {
+    type Error = BpioError;
+
+    fn encode(&mut self, flatbuffer_bytes: Vec<u8>, dst: &mut BytesMut) -> crate::Result<()> {
+        let mut cobs_encoded = cobs::encode_vec(&flatbuffer_bytes);
+        cobs_encoded.push(0x00);
+        dst.put_slice(&cobs_encoded);
+        Ok(())
+    }
+}
+
+impl Decoder for Bpio2Codec {
+    type Item = Vec<u8>;
+    type Error = BpioError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> crate::Result<Option<Vec<u8>>> {
+        let Some(sentinel) = src.iter().position(|&byte| byte == 0x00) else {
+            return Ok(None);
+        };
+
+        let frame = src.split_to(sentinel + 1);
+
+        self.decode_buf.clear();
+        self.decode_buf.resize(frame.len(), 0);
+        let report = cobs::decode(&frame[..sentinel], &mut self.decode_buf)
+            .map_err(|error| BpioError::Framing(format!("{error:?}")))?;
+        Ok(Some(self.decode_buf[..report].to_vec()))
+    }
+}
+
+/// An async session with a Bus Pirate speaking the BPIO2 protocol, built on a `Framed` transport.
+pub struct AsyncBusPirate<T> {
+    framed: Framed<T, Bpio2Codec>,
+    fbb: FlatBufferBuilder<'static>,
+}
+
+impl<T> AsyncBusPirate<T>
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+{
+    /// Wraps an already-open async transport (e.g. a `tokio_serial::SerialStream`) in an
+    /// `AsyncBusPirate` session.
+    pub fn new(transport: T) -> Self {
+        AsyncBusPirate {
+            framed: Framed::new(transport, Bpio2Codec::default()),
+            fbb: FlatBufferBuilder::new(),
+        }
+    }
+
+    /// Sends a `ConfigurationRequest`, built by `build_contents` against this session's
+    /// `FlatBufferBuilder`, and awaits the Bus Pirate's response.
+    pub async fn configure(
+        &mut self,
+        build_contents: impl FnOnce(&mut FlatBufferBuilder<'static>) -> WIPOffset<ConfigurationRequest<'static>>,
+    ) -> crate::Result<()> {
+        let contents = build_contents(&mut self.fbb);
+        self.send(RequestPacketContents::ConfigurationRequest, contents.as_union_value())
+            .await?;
+        let response_bytes = self.recv().await?;
+        let response = root_as_response_packet(&response_bytes)?;
+        match response.contents_type() {
+            ResponsePacketContents::ErrorResponse => Err(BpioError::device(
+                response.contents_as_error_response().unwrap().error(),
+            )),
+            ResponsePacketContents::ConfigurationResponse => {
+                match response.contents_as_configuration_response().unwrap().error() {
+                    Some(error) => Err(BpioError::device(Some(error))),
+                    None => Ok(()),
+                }
+            }
+            other => Err(BpioError::device(Some(&format!(
+                "unexpected response contents type {other:?}"
+            )))),
+        }
+    }
+
+    /// Sends a `DataRequest`, built by `build_contents` against this session's
+    /// `FlatBufferBuilder`, and awaits the bytes the Bus Pirate read back, if any.
+    pub async fn transfer(
+        &mut self,
+        build_contents: impl FnOnce(&mut FlatBufferBuilder<'static>) -> WIPOffset<DataRequest<'static>>,
+    ) -> crate::Result<Vec<u8>> {
+        let contents = build_contents(&mut self.fbb);
+        self.send(RequestPacketContents::DataRequest, contents.as_union_value())
+            .await?;
+        let response_bytes = self.recv().await?;
+        let response = root_as_response_packet(&response_bytes)?;
+        match response.contents_type() {
+            ResponsePacketContents::ErrorResponse => Err(BpioError::device(
+                response.contents_as_error_response().unwrap().error(),
+            )),
+            ResponsePacketContents::DataResponse => {
+                let contents = response.contents_as_data_response().unwrap();
+                if let Some(error) = contents.error() {
+                    return Err(BpioError::device(Some(error)));
+                }
+                Ok(contents
+                    .data_read()
+                    .map(|bytes| bytes.bytes().to_vec())
+                    .unwrap_or_default())
+            }
+            other => Err(BpioError::device(Some(&format!(
+                "unexpected response contents type {other:?}"
+            )))),
+        }
+    }
+
+    async fn send(
+        &mut self,
+        contents_type: RequestPacketContents,
+        contents: flatbuffers::UnionWIPOffset,
+    ) -> crate::Result<()> {
+        let packet = RequestPacket::create(
+            &mut self.fbb,
+            &RequestPacketArgs {
+                version_major: 2,
+                version_minor: 0,
+                contents_type,
+                contents: Some(contents),
+            },
+        );
+        self.fbb.finish_minimal(packet);
+        let flatbuffer_bytes = self.fbb.finished_data().to_vec();
+        self.fbb.reset();
+
+        use futures_util::SinkExt;
+        self.framed.send(flatbuffer_bytes).await
+    }
+
+    async fn recv(&mut self) -> crate::Result<Vec<u8>> {
+        match self.framed.next().await {
+            Some(frame) => frame,
+            None => Err(BpioError::Framing("transport closed".to_string())),
+        }
+    }
+}
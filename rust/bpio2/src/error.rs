@@ -0,0 +1,99 @@
+use std::fmt;
+
+/// Errors that can occur while driving a [`crate::BusPirate`] session.
+#[derive(Debug)]
+pub enum BpioError {
+    /// Reading from or writing to the underlying transport failed.
+    Io(std::io::Error),
+    /// A response's COBS framing could not be decoded.
+    Framing(String),
+    /// The flatbuffer response packet could not be parsed.
+    InvalidResponse(flatbuffers::InvalidFlatbuffer),
+    /// The Bus Pirate reported an error for the request.
+    Device(DeviceError),
+    /// The target address falls in an I2C-reserved range and was rejected before it was sent.
+    ReservedAddress(u8),
+}
+
+/// A classified failure reported by the Bus Pirate itself, as opposed to a transport or framing
+/// problem. Mirrors the abort reasons rp-hal's I2C driver distinguishes, so callers can match on
+/// e.g. a missing ACK instead of string-scraping the raw error message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceError {
+    /// The addressed device did not respond, e.g. because it isn't present or isn't ready.
+    NoAcknowledge,
+    /// The bus was lost to another controller mid-transaction.
+    ArbitrationLoss,
+    /// A write was attempted while the transmit buffer/shift register still held data.
+    TxNotEmpty,
+    /// A failure that doesn't match one of the above, with the Bus Pirate's raw message.
+    Other(String),
+}
+
+impl DeviceError {
+    /// Classifies a raw error message from an `ErrorResponse`, `ConfigurationResponse`, or
+    /// `DataResponse` into a `DeviceError`, falling back to `Other` if it doesn't recognize the
+    /// message.
+    fn from_message(message: &str) -> Self {
+        let lower = message.to_ascii_lowercase();
+        if lower.contains("no ack") || lower.contains("not acknowledge") || lower.contains("nack")
+        {
+            DeviceError::NoAcknowledge
+        } else if lower.contains("arbitration") {
+            DeviceError::ArbitrationLoss
+        } else if lower.contains("tx not empty") || lower.contains("transmit buffer") {
+            DeviceError::TxNotEmpty
+        } else {
+            DeviceError::Other(message.to_string())
+        }
+    }
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceError::NoAcknowledge => write!(f, "no acknowledge from addressed device"),
+            DeviceError::ArbitrationLoss => write!(f, "arbitration loss"),
+            DeviceError::TxNotEmpty => write!(f, "transmit buffer not empty"),
+            DeviceError::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl fmt::Display for BpioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BpioError::Io(error) => write!(f, "I/O error: {error}"),
+            BpioError::Framing(error) => write!(f, "COBS framing error: {error}"),
+            BpioError::InvalidResponse(error) => write!(f, "invalid response packet: {error}"),
+            BpioError::Device(error) => write!(f, "Bus Pirate returned an error: {error}"),
+            BpioError::ReservedAddress(address) => {
+                write!(f, "0x{address:02X} is a reserved I2C address")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BpioError {}
+
+impl From<std::io::Error> for BpioError {
+    fn from(error: std::io::Error) -> Self {
+        BpioError::Io(error)
+    }
+}
+
+impl From<flatbuffers::InvalidFlatbuffer> for BpioError {
+    fn from(error: flatbuffers::InvalidFlatbuffer) -> Self {
+        BpioError::InvalidResponse(error)
+    }
+}
+
+impl BpioError {
+    /// Builds a `BpioError::Device` from a raw error message, classifying it into a
+    /// [`DeviceError`] variant where possible.
+    pub(crate) fn device(message: Option<&str>) -> Self {
+        BpioError::Device(DeviceError::from_message(
+            message.unwrap_or("<no error message>"),
+        ))
+    }
+}
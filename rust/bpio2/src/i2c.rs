@@ -0,0 +1,293 @@
+use embedded_hal::i2c::{self, I2c, Operation, SevenBitAddress};
+
+use crate::{i2c_reserved_addr, BpioError, BusPirate, DataRequestBuilder, Transport};
+
+/// Adapts a [`BusPirate`] that has been configured for I2C mode into an
+/// [`embedded_hal::i2c::I2c`] implementor, so existing `embedded-hal` device drivers (EEPROMs,
+/// sensors, etc.) can run unchanged against a Bus Pirate.
+///
+/// Construct this *after* sending the `ConfigurationRequest` that puts the Bus Pirate into I2C
+/// mode; `BusPirateI2c` only translates transactions, it doesn't configure the mode itself.
+pub struct BusPirateI2c<T> {
+    bus_pirate: BusPirate<T>,
+}
+
+impl<T> BusPirateI2c<T>
+where
+    T: Transport,
+{
+    /// Wraps a `BusPirate` session that has already been put into I2C mode.
+    pub fn new(bus_pirate: BusPirate<T>) -> Self {
+        BusPirateI2c { bus_pirate }
+    }
+
+    /// Unwraps this adapter, returning the underlying `BusPirate` session.
+    pub fn into_inner(self) -> BusPirate<T> {
+        self.bus_pirate
+    }
+
+    /// Broadcasts `data` as an I2C general call (address `0x00`).
+    ///
+    /// `I2c::transaction` rejects address `0x00` as reserved, since the vast majority of calls
+    /// addressing it are bugs. Use this method instead when a general call is genuinely intended.
+    pub fn general_call(&mut self, data: &[u8]) -> crate::Result<()> {
+        let mut data_write = Vec::with_capacity(data.len() + 1);
+        data_write.push(0x00);
+        data_write.extend_from_slice(data);
+
+        self.bus_pirate
+            .transfer(|fbb| {
+                let data_write = fbb.create_vector::<u8>(&data_write);
+                let mut data_request = DataRequestBuilder::new(fbb);
+                data_request.add_start_main(true);
+                data_request.add_data_write(data_write);
+                data_request.add_stop_main(true);
+                data_request.finish()
+            })
+            .map(|_| ())
+    }
+}
+
+impl<T> i2c::ErrorType for BusPirateI2c<T> {
+    type Error = BpioError;
+}
+
+impl i2c::Error for BpioError {
+    fn kind(&self) -> i2c::ErrorKind {
+        match self {
+            BpioError::Device(crate::DeviceError::NoAcknowledge) => {
+                i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Unknown)
+            }
+            BpioError::Device(crate::DeviceError::ArbitrationLoss) => {
+                i2c::ErrorKind::ArbitrationLoss
+            }
+            _ => i2c::ErrorKind::Other,
+        }
+    }
+}
+
+/// Which half of an I2C transaction an `embedded_hal::i2c::Operation` represents.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OpKind {
+    Write,
+    Read,
+}
+
+impl OpKind {
+    fn of(operation: &Operation<'_>) -> Self {
+        match operation {
+            Operation::Write(_) => OpKind::Write,
+            Operation::Read(_) => OpKind::Read,
+        }
+    }
+}
+
+impl<T> I2c<SevenBitAddress> for BusPirateI2c<T>
+where
+    T: Transport,
+{
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if i2c_reserved_addr(address) {
+            return Err(BpioError::ReservedAddress(address));
+        }
+
+        let op_count = operations.len();
+        let mut previous_kind = None;
+        for (index, operation) in operations.iter_mut().enumerate() {
+            let kind = OpKind::of(operation);
+            // A (repeated) start, and the address byte that follows it, is only needed when
+            // this operation isn't a continuation of the previous one: adjacent operations of
+            // the same type must be sent back-to-back with no start in between, per
+            // `embedded_hal::i2c::I2c::transaction`'s contract.
+            let start_main = previous_kind != Some(kind);
+            let stop_main = index == op_count - 1;
+            previous_kind = Some(kind);
+
+            match operation {
+                Operation::Write(data) => {
+                    let mut data_write = Vec::with_capacity(data.len() + 1);
+                    if start_main {
+                        // The address is always supplied in `data_write`; the Bus Pirate sets
+                        // the read/not-write bit itself, so we always send the write (even)
+                        // address here, even for a transaction that is ultimately a read.
+                        data_write.push(address << 1);
+                    }
+                    data_write.extend_from_slice(data);
+
+                    self.bus_pirate.transfer(|fbb| {
+                        let data_write = fbb.create_vector::<u8>(&data_write);
+                        let mut data_request = DataRequestBuilder::new(fbb);
+                        data_request.add_start_main(start_main);
+                        data_request.add_data_write(data_write);
+                        data_request.add_stop_main(stop_main);
+                        data_request.finish()
+                    })?;
+                }
+                Operation::Read(data) => {
+                    let bytes_read = self.bus_pirate.transfer(|fbb| {
+                        let data_write =
+                            start_main.then(|| fbb.create_vector::<u8>(&[address << 1]));
+                        let mut data_request = DataRequestBuilder::new(fbb);
+                        if let Some(data_write) = data_write {
+                            data_request.add_data_write(data_write);
+                        }
+                        data_request.add_start_main(start_main);
+                        data_request.add_bytes_read(data.len() as u32);
+                        data_request.add_stop_main(stop_main);
+                        data_request.finish()
+                    })?;
+                    if bytes_read.len() != data.len() {
+                        return Err(BpioError::device(Some(&format!(
+                            "expected {} bytes from the Bus Pirate, got {}",
+                            data.len(),
+                            bytes_read.len()
+                        ))));
+                    }
+                    data.copy_from_slice(&bytes_read);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flatbuffers::FlatBufferBuilder;
+
+    use super::*;
+    use crate::MockTransport;
+
+    /// Queues a `DataResponse` carrying `data_read` (and no error) on `transport`.
+    fn queue_data_response(transport: &mut MockTransport, data_read: &[u8]) {
+        let mut fbb = FlatBufferBuilder::new();
+        let data_read = fbb.create_vector::<u8>(data_read);
+        let data_response = crate::DataResponse::create(
+            &mut fbb,
+            &crate::DataResponseArgs {
+                error: None,
+                data_read: Some(data_read),
+            },
+        );
+        let response_packet = crate::ResponsePacket::create(
+            &mut fbb,
+            &crate::ResponsePacketArgs {
+                version_major: 2,
+                version_minor: 0,
+                contents_type: crate::ResponsePacketContents::DataResponse,
+                contents: Some(data_response.as_union_value()),
+            },
+        );
+        fbb.finish_minimal(response_packet);
+        transport.queue_response(&mut fbb);
+    }
+
+    /// Decodes every request `transport` received as a `DataRequest`, returning
+    /// `(data_write bytes, start_main, stop_main, bytes_read)` for each.
+    fn decode_data_requests(transport: &MockTransport) -> Vec<(Vec<u8>, bool, bool, u32)> {
+        transport
+            .received_requests
+            .iter()
+            .map(|bytes| {
+                let packet = crate::root_as_request_packet(bytes).unwrap();
+                let data_request = packet.contents_as_data_request().unwrap();
+                (
+                    data_request
+                        .data_write()
+                        .map(|vector| vector.bytes().to_vec())
+                        .unwrap_or_default(),
+                    data_request.start_main(),
+                    data_request.stop_main(),
+                    data_request.bytes_read(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn write_sends_a_single_start_and_stop_request_with_address_prefix() {
+        let mut transport = MockTransport::new();
+        queue_data_response(&mut transport, &[]);
+        let mut i2c = BusPirateI2c::new(BusPirate::new(transport));
+
+        i2c.write(0x50, &[0x10, 0x20]).unwrap();
+
+        let transport = i2c.into_inner().into_transport();
+        let requests = decode_data_requests(&transport);
+        assert_eq!(
+            requests,
+            vec![(vec![0xA0, 0x10, 0x20], true, true, 0)],
+            "the 7-bit address 0x50 should be sent as the even write address 0xA0"
+        );
+    }
+
+    #[test]
+    fn read_sends_a_single_start_and_stop_request_with_address_prefix() {
+        let mut transport = MockTransport::new();
+        queue_data_response(&mut transport, &[0xAA, 0xBB]);
+        let mut i2c = BusPirateI2c::new(BusPirate::new(transport));
+
+        let mut buf = [0u8; 2];
+        i2c.read(0x50, &mut buf).unwrap();
+
+        assert_eq!(buf, [0xAA, 0xBB]);
+        let transport = i2c.into_inner().into_transport();
+        let requests = decode_data_requests(&transport);
+        assert_eq!(
+            requests,
+            vec![(vec![0xA0], true, true, 2)],
+            "a read-only transaction should still send the even write address and let the Bus \
+             Pirate set the read/not-write bit"
+        );
+    }
+
+    #[test]
+    fn write_read_issues_a_repeated_start_between_the_write_and_the_read() {
+        let mut transport = MockTransport::new();
+        queue_data_response(&mut transport, &[]);
+        queue_data_response(&mut transport, &[0xAA]);
+        let mut i2c = BusPirateI2c::new(BusPirate::new(transport));
+
+        let mut buf = [0u8; 1];
+        i2c.write_read(0x50, &[0x10], &mut buf).unwrap();
+
+        assert_eq!(buf, [0xAA]);
+        let transport = i2c.into_inner().into_transport();
+        let requests = decode_data_requests(&transport);
+        assert_eq!(
+            requests,
+            vec![
+                (vec![0xA0, 0x10], true, false, 0),
+                (vec![0xA0], true, true, 1),
+            ],
+            "the read half should start (repeated-start) again with the address, \
+             since it's a different operation type than the write that preceded it"
+        );
+    }
+
+    #[test]
+    fn adjacent_writes_of_the_same_type_share_a_single_start() {
+        let mut transport = MockTransport::new();
+        queue_data_response(&mut transport, &[]);
+        queue_data_response(&mut transport, &[]);
+        let mut i2c = BusPirateI2c::new(BusPirate::new(transport));
+
+        i2c.transaction(
+            0x50,
+            &mut [Operation::Write(&[0x10]), Operation::Write(&[0x20])],
+        )
+        .unwrap();
+
+        let transport = i2c.into_inner().into_transport();
+        let requests = decode_data_requests(&transport);
+        assert_eq!(
+            requests,
+            vec![(vec![0xA0, 0x10], true, false, 0), (vec![0x20], false, true, 0)],
+            "the second write continues the first one with no repeated start or address byte"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Rust bindings for the Bus Pirate's BPIO2 flatbuffer protocol.
+//!
+//! The generated flatbuffer types (`RequestPacket`, `ResponsePacketContents`, and friends) are
+//! produced from `bpio2.fbs` by `build.rs` and included below, so they live at the crate root
+//! alongside the rest of this crate's API.
+//!
+//! On top of those generated types, this crate provides [`BusPirate`], a session type that owns
+//! the transport and hides the COBS framing and request/response round-trip so callers don't have
+//! to hand-roll it for every transaction. See `examples/` for the raw flatbuffer API and the
+//! [`BusPirate`] API side by side.
+
+include!(concat!(env!("OUT_DIR"), "/bpio2_generated.rs"));
+
+mod addr;
+#[cfg(feature = "async")]
+mod asynch;
+mod error;
+mod i2c;
+mod session;
+mod transport;
+
+pub use addr::i2c_reserved_addr;
+#[cfg(feature = "async")]
+pub use asynch::{AsyncBusPirate, Bpio2Codec};
+pub use error::{BpioError, DeviceError};
+pub use i2c::BusPirateI2c;
+pub use session::BusPirate;
+pub use transport::{MockTransport, Transport};
+
+/// Convenience alias for results returned by this crate's session API.
+pub type Result<T> = std::result::Result<T, BpioError>;
@@ -0,0 +1,143 @@
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
+
+use crate::{
+    BpioError, ConfigurationRequest, DataRequest, RequestPacket, RequestPacketArgs,
+    RequestPacketContents, ResponsePacketContents, Transport, root_as_response_packet,
+};
+
+/// A session with a Bus Pirate speaking the BPIO2 protocol.
+///
+/// `BusPirate` owns the transport (typically a `Box<dyn serialport::SerialPort>`, but any
+/// `Read + Write` works) and takes care of the COBS framing and flatbuffer plumbing that every
+/// request/response round-trip needs: finishing the `FlatBufferBuilder`, COBS-encoding and
+/// sending the request, reading until the `0x00` sentinel, and decoding the response. Callers
+/// only need to build the request contents and inspect the result.
+pub struct BusPirate<T> {
+    transport: T,
+    fbb: FlatBufferBuilder<'static>,
+    read_buf: [u8; 128],
+    decode_buf: [u8; 256],
+}
+
+impl<T> BusPirate<T>
+where
+    T: Transport,
+{
+    /// Wraps an already-open transport in a `BusPirate` session.
+    pub fn new(transport: T) -> Self {
+        BusPirate {
+            transport,
+            fbb: FlatBufferBuilder::new(),
+            read_buf: [0u8; 128],
+            decode_buf: [0u8; 256],
+        }
+    }
+
+    /// Unwraps this session, returning the underlying transport.
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+
+    /// Sends a `ConfigurationRequest`, built by `build_contents` against this session's
+    /// `FlatBufferBuilder`, and waits for the Bus Pirate's response.
+    ///
+    /// Returns an error if the Bus Pirate replied with an `ErrorResponse`, or if the
+    /// `ConfigurationResponse` itself carries an error message.
+    pub fn configure(
+        &mut self,
+        build_contents: impl FnOnce(&mut FlatBufferBuilder<'static>) -> WIPOffset<ConfigurationRequest<'static>>,
+    ) -> crate::Result<()> {
+        let contents = build_contents(&mut self.fbb);
+        self.send_request(RequestPacketContents::ConfigurationRequest, contents.as_union_value())?;
+        let response = self.receive_response()?;
+        match response.contents_type() {
+            ResponsePacketContents::ErrorResponse => Err(BpioError::device(
+                response.contents_as_error_response().unwrap().error(),
+            )),
+            ResponsePacketContents::ConfigurationResponse => {
+                let contents = response.contents_as_configuration_response().unwrap();
+                match contents.error() {
+                    Some(error) => Err(BpioError::device(Some(error))),
+                    None => Ok(()),
+                }
+            }
+            other => Err(BpioError::device(Some(&format!(
+                "unexpected response contents type {other:?}"
+            )))),
+        }
+    }
+
+    /// Sends a `DataRequest`, built by `build_contents` against this session's
+    /// `FlatBufferBuilder`, and returns the bytes the Bus Pirate read back, if any.
+    ///
+    /// Returns an error if the Bus Pirate replied with an `ErrorResponse`, or if the
+    /// `DataResponse` itself carries an error message.
+    pub fn transfer(
+        &mut self,
+        build_contents: impl FnOnce(&mut FlatBufferBuilder<'static>) -> WIPOffset<DataRequest<'static>>,
+    ) -> crate::Result<Vec<u8>> {
+        let contents = build_contents(&mut self.fbb);
+        self.send_request(RequestPacketContents::DataRequest, contents.as_union_value())?;
+        let response = self.receive_response()?;
+        match response.contents_type() {
+            ResponsePacketContents::ErrorResponse => Err(BpioError::device(
+                response.contents_as_error_response().unwrap().error(),
+            )),
+            ResponsePacketContents::DataResponse => {
+                let contents = response.contents_as_data_response().unwrap();
+                if let Some(error) = contents.error() {
+                    return Err(BpioError::device(Some(error)));
+                }
+                Ok(contents
+                    .data_read()
+                    .map(|bytes| bytes.bytes().to_vec())
+                    .unwrap_or_default())
+            }
+            other => Err(BpioError::device(Some(&format!(
+                "unexpected response contents type {other:?}"
+            )))),
+        }
+    }
+
+    /// Finishes the in-flight request as a `RequestPacket`, COBS-encodes it, and writes it to
+    /// the transport.
+    fn send_request(
+        &mut self,
+        contents_type: RequestPacketContents,
+        contents: flatbuffers::UnionWIPOffset,
+    ) -> crate::Result<()> {
+        let packet = RequestPacket::create(
+            &mut self.fbb,
+            &RequestPacketArgs {
+                version_major: 2,
+                version_minor: 0,
+                contents_type,
+                contents: Some(contents),
+            },
+        );
+        self.fbb.finish_minimal(packet);
+
+        let mut cobs_encoded = cobs::encode_vec(self.fbb.finished_data());
+        cobs_encoded.push(0x00);
+        self.transport.write_all(&cobs_encoded)?;
+        self.fbb.reset();
+        Ok(())
+    }
+
+    /// Reads from the transport until a full COBS frame has arrived and parses it as a
+    /// `ResponsePacket`.
+    fn receive_response(&mut self) -> crate::Result<crate::ResponsePacket<'_>> {
+        let mut decoder = cobs::CobsDecoder::new(&mut self.decode_buf);
+        let parsed_size = loop {
+            let bytes_read = self.transport.read(&mut self.read_buf)?;
+            if let Some(report) = decoder
+                .push(&self.read_buf[..bytes_read])
+                .map_err(|error| BpioError::Framing(format!("{error:?}")))?
+            {
+                break report.parsed_size();
+            }
+        };
+        Ok(root_as_response_packet(&self.decode_buf[..parsed_size])?)
+    }
+}
+